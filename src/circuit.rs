@@ -0,0 +1,222 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use ark_bls12_381::Fr;
+use ark_ff::Field;
+use ark_relations::lc;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable};
+
+use crate::index::DummyWitness;
+
+// Our circuit y = a*x + b
+pub struct LinearCircuit {
+    pub x: Option<Fr>, // witness - private
+    pub y: Option<Fr>, // public input
+    pub a: Fr,
+    pub b: Fr,
+}
+
+impl DummyWitness for LinearCircuit {
+    /// `a` and `b` are public parameters of this demo's fixed statement
+    /// (see `main.rs`), not witness values, so the indexer needs the real
+    /// ones here too — only `x`/`y` are placeholders.
+    fn with_dummy_witness() -> Self {
+        LinearCircuit {
+            x: None,
+            y: None,
+            a: Fr::from(3u64),
+            b: Fr::from(5u64),
+        }
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for LinearCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        // Difference from Bellman is that out witness - private input has to be initialized with new_witness_variable
+        let x = cs.new_witness_variable(|| self.x.ok_or(SynthesisError::AssignmentMissing))?;
+
+        // new_input_variable bulic inputy
+        let y = cs.new_input_variable(|| self.y.ok_or(SynthesisError::AssignmentMissing))?;
+
+        // tmp = a * x
+        let tmp_val = self.x.map(|mut v| {
+            v *= self.a;
+            v
+        });
+
+        let tmp = cs.new_witness_variable(|| tmp_val.ok_or(SynthesisError::AssignmentMissing))?;
+
+        // constraint: a * x = tmp
+        cs.enforce_constraint(lc!() + (self.a, x), lc!() + Variable::One, lc!() + tmp)?;
+
+        // constraint: tmp + b = y
+        cs.enforce_constraint(
+            lc!() + tmp + (self.b, Variable::One),
+            lc!() + Variable::One,
+            lc!() + y,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod linear_circuit_tests {
+    use super::*;
+    use crate::index::constraint_matrices;
+
+    #[test]
+    fn satisfied_assignment_produces_valid_matrices() {
+        let circuit = LinearCircuit {
+            x: Some(Fr::from(11u64)),
+            y: Some(Fr::from(38u64)),
+            a: Fr::from(3u64),
+            b: Fr::from(5u64),
+        };
+
+        let matrices =
+            constraint_matrices::<Fr, _>(circuit).expect("well-formed circuit should synthesize");
+        assert_eq!(matrices.num_constraints, 2);
+    }
+}
+
+/// A single R1CS constraint `<A,z> * <B,z> = <C,z>`, given as three sparse
+/// linear combinations over the witness vector `z`. Each linear combination
+/// is a list of `(coefficient, variable_index)` pairs, where index `0`
+/// refers to `Variable::One`, indices `1..=public_inputs.len()` refer to the
+/// public inputs in order, and the remaining indices refer to the private
+/// witnesses in order.
+pub type SparseLc<F> = Vec<(F, usize)>;
+
+#[derive(Clone)]
+pub struct R1csConstraint<F: Field> {
+    pub a: SparseLc<F>,
+    pub b: SparseLc<F>,
+    pub c: SparseLc<F>,
+}
+
+/// An R1CS instance specified at runtime rather than hard-coded into a
+/// circuit type: public inputs, private witnesses, and a list of
+/// constraints referencing those values by index (see [`SparseLc`]). Lets a
+/// caller prove an arbitrary statement loaded from a file inside the
+/// enclave without recompiling, instead of being limited to the baked-in
+/// [`LinearCircuit`].
+pub struct R1csCircuit<F: Field> {
+    pub public_inputs: Vec<F>,
+    pub witnesses: Vec<F>,
+    pub constraints: Vec<R1csConstraint<F>>,
+}
+
+impl<F: Field> R1csCircuit<F> {
+    /// A placeholder instance with the same constraints and variable counts
+    /// as `self` but all input/witness values zeroed. Unlike
+    /// [`LinearCircuit`], an `R1csCircuit`'s shape is runtime data rather
+    /// than part of its type, so it can't implement [`DummyWitness`]
+    /// (whose `with_dummy_witness` takes no arguments); this is the
+    /// instance-based equivalent for driving the indexer.
+    pub fn with_dummy_values(&self) -> Self {
+        R1csCircuit {
+            public_inputs: vec![F::zero(); self.public_inputs.len()],
+            witnesses: vec![F::zero(); self.witnesses.len()],
+            constraints: self.constraints.clone(),
+        }
+    }
+}
+
+impl<F: Field> ConstraintSynthesizer<F> for R1csCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let input_vars = self
+            .public_inputs
+            .iter()
+            .map(|v| cs.new_input_variable(|| Ok(*v)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let witness_vars = self
+            .witnesses
+            .iter()
+            .map(|v| cs.new_witness_variable(|| Ok(*v)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // `constraints` is loaded from a file rather than built by trusted
+        // code, so an out-of-range variable index here is adversarial input,
+        // not a programming error — it must come back as a `SynthesisError`
+        // instead of panicking the enclave.
+        let resolve = |index: usize| -> Result<Variable, SynthesisError> {
+            if index == 0 {
+                Ok(Variable::One)
+            } else if index <= input_vars.len() {
+                input_vars
+                    .get(index - 1)
+                    .copied()
+                    .ok_or(SynthesisError::AssignmentMissing)
+            } else {
+                witness_vars
+                    .get(index - 1 - input_vars.len())
+                    .copied()
+                    .ok_or(SynthesisError::AssignmentMissing)
+            }
+        };
+
+        let to_lc = |sparse: &SparseLc<F>| -> Result<_, SynthesisError> {
+            sparse.iter().try_fold(lc!(), |acc, (coeff, index)| {
+                Ok(acc + (*coeff, resolve(*index)?))
+            })
+        };
+
+        for constraint in &self.constraints {
+            cs.enforce_constraint(
+                to_lc(&constraint.a)?,
+                to_lc(&constraint.b)?,
+                to_lc(&constraint.c)?,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod r1cs_circuit_tests {
+    use super::*;
+    use crate::index::constraint_matrices;
+
+    #[test]
+    fn out_of_range_variable_index_is_an_error_not_a_panic() {
+        // One public input (index 1) and no witnesses: index 2 names
+        // neither `Variable::One` (0), the public input (1), nor any
+        // witness, so it must fail cleanly instead of indexing past the end
+        // of `witness_vars`.
+        let circuit = R1csCircuit {
+            public_inputs: vec![Fr::from(1u64)],
+            witnesses: vec![],
+            constraints: vec![R1csConstraint {
+                a: vec![(Fr::from(1u64), 2)],
+                b: vec![(Fr::from(1u64), 0)],
+                c: vec![(Fr::from(1u64), 0)],
+            }],
+        };
+
+        assert!(constraint_matrices::<Fr, _>(circuit).is_err());
+    }
+
+    #[test]
+    fn in_range_indices_resolve_correctly() {
+        // y = x (a single copy constraint), referencing the public input
+        // (index 1) and the one witness (index 2) by their SparseLc indices.
+        let circuit = R1csCircuit {
+            public_inputs: vec![Fr::from(7u64)],
+            witnesses: vec![Fr::from(7u64)],
+            constraints: vec![R1csConstraint {
+                a: vec![(Fr::from(1u64), 2)],
+                b: vec![(Fr::from(1u64), 0)],
+                c: vec![(Fr::from(1u64), 1)],
+            }],
+        };
+
+        let matrices =
+            constraint_matrices::<Fr, _>(circuit).expect("well-formed circuit should synthesize");
+        assert_eq!(matrices.num_constraints, 1);
+    }
+}
@@ -0,0 +1,207 @@
+//! Entropy sources abstracted behind a trait so the same proving/indexing
+//! code can run inside an SGX enclave, seeded from the hardware RNG, and in
+//! host-side unit tests, seeded from the OS, selected by Cargo feature: an
+//! enclave can't link the OS entropy path `thread_rng()` relies on (see the
+//! module docs in `main.rs`), but a test binary has no hardware RNG to rely
+//! on either, so `SgxCsprng` itself picks its source via `fresh_seed` below
+//! instead of always going straight to RDRAND/RDSEED.
+
+use rand::{CryptoRng, Error as RandError, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rdrand::{RdRand, RdSeed};
+
+/// How many times to retry a single RDRAND/RDSEED draw before giving up on
+/// that source. The RDRAND instruction can transiently fail (carry flag
+/// clear under heavy load); Intel's guidance is to retry a bounded number
+/// of times rather than treat one failure as "no entropy available".
+const WORD_RETRIES: u32 = 10;
+
+/// Reseed `SgxCsprng` after this many bytes of output, so a single enclave
+/// session producing many proofs doesn't stream an unbounded amount of
+/// output from one hardware seed.
+const DEFAULT_RESEED_INTERVAL_BYTES: u64 = 1 << 20;
+
+/// Errors from hardware entropy collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntropyError {
+    /// Neither RDRAND nor its RDSEED fallback produced a value within
+    /// `WORD_RETRIES` attempts.
+    HardwareRngUnavailable,
+}
+
+/// A source of cryptographically secure entropy, used to seed the RNG that
+/// feeds `MarlinInstance::setup`/`index`/`prove`/`verify`.
+pub trait EntropySource {
+    /// Produce 32 bytes of fresh entropy.
+    fn try_seed(&mut self) -> Result<[u8; 32], EntropyError>;
+
+    /// Seed a `ChaCha20Rng` from this source.
+    fn rng(&mut self) -> Result<ChaCha20Rng, EntropyError> {
+        Ok(ChaCha20Rng::from_seed(self.try_seed()?))
+    }
+}
+
+/// Draws a 32-byte seed word-by-word from RDRAND, retrying each word up to
+/// `WORD_RETRIES` times before falling back to RDSEED.
+fn hardware_seed() -> Result<[u8; 32], EntropyError> {
+    let mut seed = [0u8; 32];
+    let mut rdrand = RdRand::new().ok();
+    let mut rdseed = RdSeed::new().ok();
+
+    for word in seed.chunks_mut(4) {
+        let drawn = rdrand
+            .as_mut()
+            .map(|source| retry_fill(source, word))
+            .unwrap_or(false)
+            || rdseed
+                .as_mut()
+                .map(|source| retry_fill(source, word))
+                .unwrap_or(false);
+
+        if !drawn {
+            return Err(EntropyError::HardwareRngUnavailable);
+        }
+    }
+
+    Ok(seed)
+}
+
+fn retry_fill(rng: &mut impl RngCore, word: &mut [u8]) -> bool {
+    (0..WORD_RETRIES).any(|_| rng.try_fill_bytes(word).is_ok())
+}
+
+/// The entropy `SgxCsprng` actually seeds and reseeds itself from: real
+/// hardware entropy everywhere except `std` builds (host-side tests and
+/// tooling), where RDRAND/RDSEED may not be available and `GetRandomSource`
+/// is used instead. This is the one place that picks between the two, so
+/// `SgxCsprng`'s own logic stays the same in-enclave and under test.
+#[cfg(not(feature = "std"))]
+fn fresh_seed() -> Result<[u8; 32], EntropyError> {
+    hardware_seed()
+}
+
+#[cfg(feature = "std")]
+fn fresh_seed() -> Result<[u8; 32], EntropyError> {
+    GetRandomSource.try_seed()
+}
+
+/// Hardware entropy via RDRAND, falling back to RDSEED, exposed through the
+/// `rdrand` crate. The only source available inside an SGX enclave.
+#[derive(Default)]
+pub struct RdRandSource;
+
+impl EntropySource for RdRandSource {
+    fn try_seed(&mut self) -> Result<[u8; 32], EntropyError> {
+        hardware_seed()
+    }
+}
+
+/// `getrandom`-backed source for std/test builds, where RDRAND may not be
+/// available (e.g. a CI runner without the instruction, or a non-SGX host).
+#[cfg(feature = "std")]
+#[derive(Default)]
+pub struct GetRandomSource;
+
+#[cfg(feature = "std")]
+impl EntropySource for GetRandomSource {
+    fn try_seed(&mut self) -> Result<[u8; 32], EntropyError> {
+        let mut seed = [0u8; 32];
+        getrandom::getrandom(&mut seed).map_err(|_| EntropyError::HardwareRngUnavailable)?;
+        Ok(seed)
+    }
+}
+
+/// A long-lived hardware-seeded CSPRNG for use inside the enclave. Wraps
+/// `ChaCha20Rng` and automatically reseeds from a fresh `fresh_seed` draw
+/// (RDRAND/RDSEED without the `std` feature, `getrandom` with it) after
+/// `reseed_after_bytes` bytes of output, so one enclave session can
+/// drive `setup`/`index`/`prove`/`verify` across many proofs without ever
+/// running an unbounded stream off a single seed. Implements
+/// `CryptoRng + RngCore`, so it drops into `MarlinInstance` unchanged.
+pub struct SgxCsprng {
+    rng: ChaCha20Rng,
+    reseed_after_bytes: u64,
+    bytes_since_reseed: u64,
+}
+
+impl SgxCsprng {
+    /// Reseed every [`DEFAULT_RESEED_INTERVAL_BYTES`] bytes of output.
+    pub fn new() -> Result<Self, EntropyError> {
+        Self::with_reseed_interval(DEFAULT_RESEED_INTERVAL_BYTES)
+    }
+
+    pub fn with_reseed_interval(reseed_after_bytes: u64) -> Result<Self, EntropyError> {
+        Ok(Self {
+            rng: ChaCha20Rng::from_seed(fresh_seed()?),
+            reseed_after_bytes,
+            bytes_since_reseed: 0,
+        })
+    }
+
+    fn note_output(&mut self, bytes_produced: u64) {
+        self.bytes_since_reseed += bytes_produced;
+        if self.bytes_since_reseed < self.reseed_after_bytes {
+            return;
+        }
+        // Best effort: if fresh entropy is briefly unavailable we keep
+        // streaming from the current ChaCha20 state rather than failing a
+        // proof that's already in flight.
+        if let Ok(seed) = fresh_seed() {
+            self.rng = ChaCha20Rng::from_seed(seed);
+        }
+        self.bytes_since_reseed = 0;
+    }
+
+    /// The number of output bytes produced since the last reseed, for tests
+    /// that need to confirm a reseed actually happened at the boundary.
+    #[cfg(test)]
+    pub(crate) fn bytes_since_reseed(&self) -> u64 {
+        self.bytes_since_reseed
+    }
+}
+
+impl RngCore for SgxCsprng {
+    fn next_u32(&mut self) -> u32 {
+        let value = self.rng.next_u32();
+        self.note_output(4);
+        value
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value = self.rng.next_u64();
+        self.note_output(8);
+        value
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+        self.note_output(dest.len() as u64);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RandError> {
+        self.rng.try_fill_bytes(dest)?;
+        self.note_output(dest.len() as u64);
+        Ok(())
+    }
+}
+
+impl CryptoRng for SgxCsprng {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reseeds_after_the_configured_byte_interval() {
+        let mut rng = SgxCsprng::with_reseed_interval(16).expect("GetRandomSource should seed ok");
+
+        let mut buf = [0u8; 10];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(rng.bytes_since_reseed(), 10);
+
+        // Crossing the 16-byte interval should trigger a reseed, resetting
+        // the counter rather than letting it grow past the threshold.
+        rng.fill_bytes(&mut buf);
+        assert_eq!(rng.bytes_since_reseed(), 0);
+    }
+}
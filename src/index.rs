@@ -0,0 +1,76 @@
+//! Helpers for driving Marlin's indexer without hand-duplicating field
+//! values: a `DummyWitness` trait that produces a placeholder instance of a
+//! circuit purely from its shape, and a function that runs constraint
+//! generation against a fresh constraint system to recover the R1CS
+//! matrices for inspection or serialization.
+
+use ark_ff::PrimeField;
+use ark_relations::r1cs::{
+    ConstraintMatrices, ConstraintSynthesizer, ConstraintSystem, SynthesisError, SynthesisMode,
+};
+
+/// A circuit that can produce a placeholder instance of itself, carrying no
+/// real witness values but the same shape (same constraints, same variable
+/// count) as the statement being proved. Marlin's indexer only needs that
+/// shape, not the values, so this replaces hand-built
+/// `empty_circuit { x: None, .. }` literals at each call site.
+pub trait DummyWitness: Sized {
+    fn with_dummy_witness() -> Self;
+}
+
+/// Runs `circuit`'s constraint generation against a fresh constraint system
+/// and returns the resulting R1CS matrices (the A/B/C matrices, and the
+/// constraint/instance/witness counts) — e.g. for inspecting or serializing
+/// the circuit shape ahead of a setup ceremony, without going through
+/// `MarlinInstance::index`.
+///
+/// `circuit` can be an `R1csCircuit` loaded from untrusted enclave input, so
+/// a malformed circuit (e.g. an out-of-range variable index) must come back
+/// as an `Err` here too, rather than panicking one layer above where
+/// `R1csCircuit::generate_constraints` already turned that into a
+/// `SynthesisError`.
+pub fn constraint_matrices<F: PrimeField, C: ConstraintSynthesizer<F>>(
+    circuit: C,
+) -> Result<ConstraintMatrices<F>, SynthesisError> {
+    let cs = ConstraintSystem::<F>::new_ref();
+    // `circuit` is expected to be a dummy/placeholder instance here (its
+    // witness values may be `None`), same as what `Marlin::index` itself
+    // drives internally — `Setup` mode skips evaluating witness-value
+    // closures instead of failing on the missing assignment.
+    cs.set_mode(SynthesisMode::Setup);
+    circuit.generate_constraints(cs.clone())?;
+    cs.finalize();
+    cs.to_matrices()
+        .ok_or(SynthesisError::MalformedVerifyingKey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{LinearCircuit, R1csCircuit, R1csConstraint};
+    use ark_bls12_381::Fr;
+
+    #[test]
+    fn dummy_witness_matrices_match_the_circuit_shape() {
+        let matrices = constraint_matrices::<Fr, _>(LinearCircuit::with_dummy_witness())
+            .expect("dummy witness should synthesize cleanly");
+        assert_eq!(matrices.num_constraints, 2);
+        assert_eq!(matrices.num_instance_variables, 2);
+        assert_eq!(matrices.num_witness_variables, 2);
+    }
+
+    #[test]
+    fn malformed_r1cs_circuit_returns_an_error_instead_of_panicking() {
+        let circuit = R1csCircuit {
+            public_inputs: vec![Fr::from(1u64)],
+            witnesses: vec![],
+            constraints: vec![R1csConstraint {
+                a: vec![(Fr::from(1u64), 5)], // no variable at index 5
+                b: vec![(Fr::from(1u64), 0)],
+                c: vec![(Fr::from(1u64), 0)],
+            }],
+        };
+
+        assert!(constraint_matrices::<Fr, _>(circuit).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+//! Enclave-native core: the circuit definitions, the `MarlinInstance`
+//! wrapper and the entropy sources that `main.rs` drives. Built `no_std` +
+//! `alloc` when the default `std` feature is turned off, as an SGX enclave
+//! build does since enclaves can't link the full std runtime; the `std`
+//! feature (on by default, used by host-side builds and tests) pulls std
+//! back in and enables the `getrandom`-backed entropy source.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod circuit;
+pub mod entropy;
+pub mod index;
+pub mod marlin_instance;
+pub mod transcript;
@@ -0,0 +1,297 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::PrimeField;
+use ark_marlin::{Error, IndexProverKey, IndexVerifierKey, Marlin, Proof, UniversalSRS};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{sonic_pc::SonicKZG10, PolynomialCommitment};
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::{CryptoRng, RngCore};
+use blake2::Blake2s;
+use digest::Digest;
+
+use crate::transcript::{rng_from_transcript, FiatShamir};
+
+/// The error type every `Marlin<F, PC, D>` operation returns, named so the
+/// trait below doesn't have to spell out `Error<<PC as
+/// PolynomialCommitment<...>>::Error>` at every method signature.
+type MarlinError<F, PC> = Error<<PC as PolynomialCommitment<F, DensePolynomial<F>>>::Error>;
+
+/// Shorthand for the `Result<T, MarlinError<F, PC>>` every method below
+/// returns.
+type MarlinResult<T, F, PC> = Result<T, MarlinError<F, PC>>;
+
+/// The prover/verifier key pair [`MarlinInstance::index`] produces.
+type IndexKeys<F, PC> = (IndexProverKey<F, PC>, IndexVerifierKey<F, PC>);
+
+/// One public-input/proof pair, as passed to [`MarlinInstance::verify_many`].
+type ProofInstance<F, PC> = (Vec<F>, Proof<F, PC>);
+
+/// Collapses the `Marlin<F, PC, D>` generic triple into a single type so
+/// call sites no longer have to spell out the curve / polynomial-commitment
+/// / Fiat-Shamir-hash combination at every use (this is the thing the old
+/// `TODO: CREATE type MerlinInstance` in `main.rs` was asking for).
+///
+/// Implementors fix `Field`, `PC` and `Hash` once; the rest of the crate can
+/// stay generic over `I: MarlinInstance` and swap the curve/PC/hash params
+/// by swapping the implementor.
+///
+/// The `type_complexity` lint still fires on these signatures even behind
+/// the `MarlinError`/`MarlinResult` aliases above, since it scores the
+/// resolved type rather than the alias as written — the nesting comes from
+/// `ark_poly_commit::PolynomialCommitment`'s own associated `Error` type,
+/// not from anything this trait adds, so it's suppressed here rather than
+/// chased with more aliases that wouldn't change the score.
+#[allow(clippy::type_complexity)]
+pub trait MarlinInstance {
+    type Field: PrimeField;
+    type PC: PolynomialCommitment<Self::Field, DensePolynomial<Self::Field>>;
+    type Hash: Digest;
+
+    fn setup<R: RngCore + CryptoRng>(
+        num_constraints: usize,
+        num_variables: usize,
+        rng: &mut R,
+    ) -> MarlinResult<UniversalSRS<Self::Field, Self::PC>, Self::Field, Self::PC> {
+        Marlin::<Self::Field, Self::PC, Self::Hash>::universal_setup(
+            num_constraints,
+            num_variables,
+            3 * num_constraints,
+            rng,
+        )
+    }
+
+    fn index<C: ConstraintSynthesizer<Self::Field>>(
+        srs: &UniversalSRS<Self::Field, Self::PC>,
+        circuit: C,
+    ) -> MarlinResult<IndexKeys<Self::Field, Self::PC>, Self::Field, Self::PC> {
+        Marlin::<Self::Field, Self::PC, Self::Hash>::index(srs, circuit)
+    }
+
+    fn prove<C: ConstraintSynthesizer<Self::Field>, R: RngCore + CryptoRng>(
+        pk: &IndexProverKey<Self::Field, Self::PC>,
+        circuit: C,
+        rng: &mut R,
+    ) -> MarlinResult<Proof<Self::Field, Self::PC>, Self::Field, Self::PC> {
+        Marlin::<Self::Field, Self::PC, Self::Hash>::prove(pk, circuit, rng)
+    }
+
+    fn verify<R: RngCore + CryptoRng>(
+        vk: &IndexVerifierKey<Self::Field, Self::PC>,
+        public_input: &[Self::Field],
+        proof: &Proof<Self::Field, Self::PC>,
+        rng: &mut R,
+    ) -> MarlinResult<bool, Self::Field, Self::PC> {
+        Marlin::<Self::Field, Self::PC, Self::Hash>::verify(vk, public_input, proof, rng)
+    }
+
+    /// Like [`MarlinInstance::prove`], but folds the circuit's public inputs
+    /// (and whatever domain-separation labels/context the caller already
+    /// absorbed) into `transcript`, then mixes the resulting transcript
+    /// state with real entropy from `entropy` to seed the rng `prove` runs
+    /// on. Letting the rng depend on `transcript` — rather than passing
+    /// `entropy` straight through — is what lets several proofs made in one
+    /// enclave session share and extend one running transcript instead of
+    /// each drawing unrelated randomness; still mixing in `entropy` is what
+    /// keeps that rng unpredictable, since the transcript state alone is
+    /// built only from public labels and public inputs (see
+    /// [`crate::transcript::rng_from_transcript`]).
+    fn prove_with_transcript<C: ConstraintSynthesizer<Self::Field>>(
+        pk: &IndexProverKey<Self::Field, Self::PC>,
+        circuit: C,
+        public_input: &[Self::Field],
+        transcript: &mut impl FiatShamir<Self::Field>,
+        entropy: &mut (impl RngCore + CryptoRng),
+    ) -> MarlinResult<Proof<Self::Field, Self::PC>, Self::Field, Self::PC> {
+        for input in public_input {
+            let mut bytes = Vec::new();
+            input
+                .serialize(&mut bytes)
+                .expect("serializing a field element cannot fail");
+            transcript.absorb(b"marlin-prove/public-input", &bytes);
+        }
+        let mut rng = rng_from_transcript(transcript, entropy);
+        Self::prove(pk, circuit, &mut rng)
+    }
+
+    /// The verifier-side counterpart to [`MarlinInstance::prove_with_transcript`].
+    /// The caller must replay the same `absorb` calls (same labels, same
+    /// bytes, same order) that were made before proving so `transcript`
+    /// reaches an identical state here. `entropy` only needs to be *some*
+    /// rng the verifier controls — it doesn't have to be the one the prover
+    /// used — since here it's just randomizing this verifier's own pairing
+    /// check, not reproducing the prover's blinding.
+    fn verify_with_transcript(
+        vk: &IndexVerifierKey<Self::Field, Self::PC>,
+        public_input: &[Self::Field],
+        proof: &Proof<Self::Field, Self::PC>,
+        transcript: &mut impl FiatShamir<Self::Field>,
+        entropy: &mut (impl RngCore + CryptoRng),
+    ) -> MarlinResult<bool, Self::Field, Self::PC> {
+        for input in public_input {
+            let mut bytes = Vec::new();
+            input
+                .serialize(&mut bytes)
+                .expect("serializing a field element cannot fail");
+            transcript.absorb(b"marlin-prove/public-input", &bytes);
+        }
+        let mut rng = rng_from_transcript(transcript, entropy);
+        Self::verify(vk, public_input, proof, &mut rng)
+    }
+
+    /// Checks several proofs against a shared verifying key, stopping at the
+    /// first one that fails instead of running every `verify` call to
+    /// completion. Deliberately not named (or documented as) a "batch"
+    /// verifier: a real pairing-batched check would sample independent
+    /// scalars `rho_i` and fold every proof's opening check into one
+    /// randomized equation `sum(rho_i * check_i) = 0`, so a single pairing
+    /// product gets evaluated instead of `N`. That folding needs the raw
+    /// per-proof opening claims (commitments, query points, evaluations)
+    /// that `ark_marlin`'s verifier builds and consumes internally and
+    /// doesn't expose, and even with them, each proof's claims sit at
+    /// independent, proof-specific Fiat-Shamir query points, so there's no
+    /// shared structure across proofs to fold without reimplementing
+    /// Marlin's verifier from scratch — not something this crate should take
+    /// on. So: an all-valid run here costs exactly `N` full `verify` calls,
+    /// the same as calling `verify` in a loop; the only saving is skipping
+    /// the remaining calls once one proof is known to be invalid.
+    fn verify_many<R: RngCore + CryptoRng>(
+        vk: &IndexVerifierKey<Self::Field, Self::PC>,
+        instances: &[ProofInstance<Self::Field, Self::PC>],
+        rng: &mut R,
+    ) -> MarlinResult<VerifyManyResult, Self::Field, Self::PC> {
+        for (i, (public_input, proof)) in instances.iter().enumerate() {
+            if !Self::verify(vk, public_input, proof, rng)? {
+                return Ok(VerifyManyResult {
+                    all_valid: false,
+                    first_failure: Some(i),
+                });
+            }
+        }
+
+        Ok(VerifyManyResult {
+            all_valid: true,
+            first_failure: None,
+        })
+    }
+}
+
+/// The result of [`MarlinInstance::verify_many`].
+pub struct VerifyManyResult {
+    /// Whether every proof verified.
+    pub all_valid: bool,
+    /// The index of the first proof that failed verification, if any.
+    pub first_failure: Option<usize>,
+}
+
+/// The curve / commitment / hash combination this crate has always used:
+/// BLS12-381 + SonicKZG10 + Blake2s.
+pub struct DefaultMarlinInstance;
+
+impl MarlinInstance for DefaultMarlinInstance {
+    type Field = Fr;
+    type PC = SonicKZG10<Bls12_381, DensePolynomial<Fr>>;
+    type Hash = Blake2s;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::LinearCircuit;
+    use crate::index::DummyWitness;
+    use crate::transcript::Blake2sTranscript;
+    use ark_std::test_rng;
+
+    fn setup_and_index() -> (
+        IndexProverKey<Fr, <DefaultMarlinInstance as MarlinInstance>::PC>,
+        IndexVerifierKey<Fr, <DefaultMarlinInstance as MarlinInstance>::PC>,
+    ) {
+        let mut rng = test_rng();
+        let srs = DefaultMarlinInstance::setup(2, 3, &mut rng).expect("universal setup failed");
+        DefaultMarlinInstance::index(&srs, LinearCircuit::with_dummy_witness())
+            .expect("indexing failed")
+    }
+
+    #[test]
+    fn prove_then_verify_round_trips() {
+        let (pk, vk) = setup_and_index();
+        let mut rng = test_rng();
+
+        let circuit = LinearCircuit {
+            x: Some(Fr::from(11u64)),
+            y: Some(Fr::from(38u64)),
+            a: Fr::from(3u64),
+            b: Fr::from(5u64),
+        };
+        let proof = DefaultMarlinInstance::prove(&pk, circuit, &mut rng).expect("proving failed");
+
+        let ok = DefaultMarlinInstance::verify(&vk, &[Fr::from(38u64)], &proof, &mut rng)
+            .expect("verification failed");
+        assert!(ok);
+    }
+
+    #[test]
+    fn prove_with_transcript_then_verify_with_transcript_round_trips() {
+        let (pk, vk) = setup_and_index();
+        let mut entropy = test_rng();
+
+        let circuit = LinearCircuit {
+            x: Some(Fr::from(11u64)),
+            y: Some(Fr::from(38u64)),
+            a: Fr::from(3u64),
+            b: Fr::from(5u64),
+        };
+        let public_input = [Fr::from(38u64)];
+
+        let mut prover_transcript = Blake2sTranscript::new(b"marlin-instance-test");
+        let proof = DefaultMarlinInstance::prove_with_transcript(
+            &pk,
+            circuit,
+            &public_input,
+            &mut prover_transcript,
+            &mut entropy,
+        )
+        .expect("proving failed");
+
+        let mut verifier_transcript = Blake2sTranscript::new(b"marlin-instance-test");
+        let ok = DefaultMarlinInstance::verify_with_transcript(
+            &vk,
+            &public_input,
+            &proof,
+            &mut verifier_transcript,
+            &mut entropy,
+        )
+        .expect("verification failed");
+        assert!(ok);
+    }
+
+    #[test]
+    fn verify_many_reports_the_index_of_the_first_invalid_proof() {
+        let (pk, vk) = setup_and_index();
+        let mut rng = test_rng();
+
+        let mut make_proof = || {
+            let circuit = LinearCircuit {
+                x: Some(Fr::from(11u64)),
+                y: Some(Fr::from(38u64)),
+                a: Fr::from(3u64),
+                b: Fr::from(5u64),
+            };
+            DefaultMarlinInstance::prove(&pk, circuit, &mut rng).expect("proving failed")
+        };
+
+        // The second proof is checked against the wrong public input, so
+        // it's invalid without needing a circuit that's actually unsound.
+        let instances = vec![
+            (vec![Fr::from(38u64)], make_proof()),
+            (vec![Fr::from(39u64)], make_proof()),
+        ];
+
+        let result = DefaultMarlinInstance::verify_many(&vk, &instances, &mut rng)
+            .expect("verify_many failed");
+        assert!(!result.all_valid);
+        assert_eq!(result.first_failure, Some(1));
+    }
+}